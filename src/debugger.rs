@@ -0,0 +1,29 @@
+pub mod debugger {
+    use std::collections::HashSet;
+
+    /// Opt-in breakpoint tracking attached to a `Computer`. Carries no
+    /// reference to the computer itself; `Computer::debug` is the loop that
+    /// drives stepping and consults it.
+    #[derive(Debug, Default)]
+    pub struct Debugger {
+        breakpoints: HashSet<usize>,
+    }
+
+    impl Debugger {
+        pub fn new() -> Debugger {
+            Debugger { breakpoints: HashSet::new() }
+        }
+
+        pub fn set_breakpoint(&mut self, addr: usize) {
+            self.breakpoints.insert(addr);
+        }
+
+        pub fn clear_breakpoint(&mut self, addr: usize) {
+            self.breakpoints.remove(&addr);
+        }
+
+        pub fn has_breakpoint(&self, addr: usize) -> bool {
+            self.breakpoints.contains(&addr)
+        }
+    }
+}
@@ -0,0 +1,82 @@
+pub mod disassembler {
+    use crate::instruction::instruction::{Instruction, ParameterMode};
+
+    fn format_operand(mode: &ParameterMode, value: i64) -> String {
+        match mode {
+            ParameterMode::Position => format!("ARG{}", value),
+            ParameterMode::Immediate => format!("#{}", value),
+            ParameterMode::Relative => format!("@{}", value),
+        }
+    }
+
+    /// Walks `program` linearly from address 0, decoding each word as an
+    /// instruction via `Instruction::try_from` and formatting its mnemonic
+    /// plus resolved operands. Words that don't decode are rendered as
+    /// `DATA <n>` and the cursor advances by one, so the listing never gets
+    /// stuck on embedded string/number data.
+    pub fn disassemble(program: &[i64]) -> Vec<(usize, String)> {
+        let mut listing = Vec::new();
+        let mut cursor = 0;
+
+        while cursor < program.len() {
+            let start = cursor;
+
+            match Instruction::try_from(program[cursor]) {
+                Ok(instruction) => {
+                    let parameter_count = instruction.parameter_modes.len();
+
+                    let mut line = format!("{}", instruction.op_code);
+                    for (offset, mode) in instruction.parameter_modes.iter().enumerate() {
+                        let value = program.get(cursor + 1 + offset).copied().unwrap_or(0);
+                        line.push(' ');
+                        line.push_str(&format_operand(mode, value));
+                    }
+
+                    listing.push((start, line));
+                    cursor += 1 + parameter_count;
+                }
+                Err(_) => {
+                    listing.push((start, format!("DATA {}", program[cursor])));
+                    cursor += 1;
+                }
+            }
+        }
+
+        listing
+    }
+
+    pub fn print_listing(program: &[i64]) {
+        for (addr, line) in disassemble(program) {
+            println!("{:04}: {}", addr, line);
+        }
+    }
+
+    mod tests {
+        use super::disassemble;
+
+        #[test]
+        fn test_disassemble_day_5_style_program() {
+            let listing = disassemble(&[1002, 4, 3, 4, 99]);
+            assert_eq!(
+                listing,
+                vec![
+                    (0, "MUL ARG4 #3 ARG4".to_string()),
+                    (4, "HALT".to_string()),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_disassemble_renders_undecodable_words_as_data() {
+            let listing = disassemble(&[3, 0, -67, 99]);
+            assert_eq!(
+                listing,
+                vec![
+                    (0, "IN ARG0".to_string()),
+                    (2, "DATA -67".to_string()),
+                    (3, "HALT".to_string()),
+                ]
+            );
+        }
+    }
+}
@@ -0,0 +1,24 @@
+pub mod error {
+    use std::fmt::{Display, Formatter};
+
+    #[derive(Debug, PartialEq)]
+    pub enum ExecutionError {
+        InvalidAddress,
+        AlreadyHalted,
+        UnknownOpcode(i64),
+        UnknownMode(u8),
+    }
+
+    impl Display for ExecutionError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            match self {
+                ExecutionError::InvalidAddress => write!(f, "invalid address"),
+                ExecutionError::AlreadyHalted => write!(f, "computer has already halted"),
+                ExecutionError::UnknownOpcode(code) => write!(f, "unknown opcode: {}", code),
+                ExecutionError::UnknownMode(mode) => write!(f, "unknown parameter mode: {}", mode),
+            }
+        }
+    }
+
+    impl std::error::Error for ExecutionError {}
+}
@@ -1,6 +1,7 @@
 pub mod instruction {
     use std::fmt::{Display, Formatter};
     use crate::opcode::opcode::OpCode;
+    use crate::error::error::ExecutionError;
 
     #[derive(Debug)]
     pub enum ParameterMode {
@@ -34,17 +35,13 @@ pub mod instruction {
         }
     }
 
-    impl TryFrom<i32> for Instruction {
-        type Error = &'static str;
+    impl TryFrom<i64> for Instruction {
+        type Error = ExecutionError;
 
-        fn try_from(value: i32) -> Result<Self, Self::Error> {
+        fn try_from(value: i64) -> Result<Self, Self::Error> {
             let numeric_op_code = value % 100;
-            if !(0..=9).contains(&numeric_op_code) && value != 99 {
-                return Err("Invalid instruction");
-            }
 
-            let value = value as u32;
-            let op_code = match value % 100 {
+            let op_code = match numeric_op_code {
                 1 => OpCode::Add,
                 2 => OpCode::Mul,
                 3 => OpCode::Input,
@@ -55,7 +52,7 @@ pub mod instruction {
                 8 => OpCode::StoreIfEquals,
                 9 => OpCode::IncrementRelativeBase,
                 99 => OpCode::Halt,
-                _ => Err("Invalid instruction")?
+                _ => return Err(ExecutionError::UnknownOpcode(value)),
             };
 
             let mut parameter_modes = Vec::new();
@@ -67,11 +64,12 @@ pub mod instruction {
             };
 
             for param in 0..parameter_count {
-                let mode = match value / 10_u32.pow(param as u32 + 2) % 10 {
+                let mode_digit = (value / 10_i64.pow(param as u32 + 2) % 10) as u8;
+                let mode = match mode_digit {
                     0 => ParameterMode::Position,
                     1 => ParameterMode::Immediate,
                     2 => ParameterMode::Relative,
-                    _ => unreachable!(),
+                    _ => return Err(ExecutionError::UnknownMode(mode_digit)),
                 };
 
                 parameter_modes.push(mode);
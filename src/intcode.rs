@@ -1,19 +1,38 @@
 pub mod computer {
-    use std::collections::VecDeque;
+    use std::io::{self, BufRead, Write};
     use crate::opcode::opcode::OpCode;
     use crate::instruction::instruction::{ParameterMode, Instruction};
     use crate::memory::memory::{ComputerMemory, RangeAddressable};
+    use crate::error::error::ExecutionError;
+    use crate::io::io::{Input, Output};
+    use crate::debugger::debugger::Debugger;
+    use crate::disassembler::disassembler;
+    use crate::loader::loader::parse_program;
+
+    #[derive(Debug, PartialEq)]
+    pub enum ComputeResult {
+        Halted,
+        NeedsInput,
+    }
 
-    pub struct Computer {
+    pub struct Computer<I, O> {
         pub memory: ComputerMemory,
         instruction_pointer: usize,
-        relative_base: usize,
-        input: VecDeque<i32>,
-        pub output: Vec<i32>,
+        relative_base: i64,
+        input: I,
+        pub output: O,
+        debugger: Option<Debugger>,
+        halted: bool,
+    }
+
+    impl<I: Input> Computer<I, Vec<i64>> {
+        pub fn take_output(&mut self) -> Vec<i64> {
+            std::mem::take(&mut self.output)
+        }
     }
 
-    impl Computer {
-        pub(crate) fn new(initial_memory: Vec<i32>, input: Vec<i32>) -> Computer {
+    impl<I: Input, O: Output> Computer<I, O> {
+        pub(crate) fn new(initial_memory: Vec<i64>, input: I, output: O) -> Computer<I, O> {
             let mut memory = ComputerMemory::new();
             memory.write_range(0..initial_memory.len(), initial_memory);
 
@@ -21,20 +40,44 @@ pub mod computer {
                 memory,
                 instruction_pointer: 0,
                 relative_base: 0,
-                input: VecDeque::from(input),
-                output: vec![],
+                input,
+                output,
+                debugger: None,
+                halted: false,
             }
         }
 
-        fn read_memory(&self, addr: usize) -> i32 {
+        pub fn from_str(source: &str, input: I, output: O) -> Result<Computer<I, O>, std::num::ParseIntError> {
+            let initial_memory = parse_program(source)?;
+            Ok(Computer::new(initial_memory, input, output))
+        }
+
+        pub fn attach_debugger(&mut self) {
+            self.debugger = Some(Debugger::new());
+        }
+
+        fn decode_at(&self, addr: usize) -> String {
+            let window = self.memory.read_range(addr..addr + 4);
+            disassembler::disassemble(&window)
+                .into_iter()
+                .next()
+                .map(|(_, line)| line)
+                .unwrap_or_else(|| "DATA ?".to_string())
+        }
+
+        fn read_memory(&self, addr: usize) -> i64 {
             *self.memory.get(&addr).unwrap_or(&0)
         }
 
-        fn write_memory(&mut self, addr: usize, value: i32) {
+        fn write_memory(&mut self, addr: usize, value: i64) {
             self.memory.insert(addr, value);
         }
 
-        fn read_addr(&mut self, mode: &ParameterMode) -> usize {
+        pub fn push_input(&mut self, value: i64) {
+            self.input.push(value);
+        }
+
+        fn read_addr(&mut self, mode: &ParameterMode) -> Result<usize, ExecutionError> {
             let value = self.read_memory(self.instruction_pointer);
             self.instruction_pointer += 1;
 
@@ -42,139 +85,246 @@ pub mod computer {
                 ParameterMode::Position | ParameterMode::Immediate => {
                     value
                 },
-                ParameterMode::Relative => self.relative_base as i32 + value
+                ParameterMode::Relative => self.relative_base + value
             };
 
             if addr < 0 {
-                panic!("Invalid address: {}", addr);
+                return Err(ExecutionError::InvalidAddress);
             }
 
-            addr as usize
+            Ok(addr as usize)
         }
 
-        fn read_value(&mut self, mode: &ParameterMode) -> i32 {
-            return match mode {
+        fn read_value(&mut self, mode: &ParameterMode) -> Result<i64, ExecutionError> {
+            match mode {
                 ParameterMode::Immediate => {
                     let value = self.read_memory(self.instruction_pointer);
                     self.instruction_pointer += 1;
-                    value
+                    Ok(value)
                 }
                 _ => {
-                    let addr = self.read_addr(mode);
-                    self.read_memory(addr)
+                    let addr = self.read_addr(mode)?;
+                    Ok(self.read_memory(addr))
                 }
             }
         }
 
-        fn step_forward(&mut self) -> bool {
-            let instruction = Instruction::try_from(self.read_memory(self.instruction_pointer))
-                .expect("Failed to decode instruction");
+        fn step_forward(&mut self) -> Result<Option<ComputeResult>, ExecutionError> {
+            if self.halted {
+                return Err(ExecutionError::AlreadyHalted);
+            }
 
-            print!("[IP: {} RB: {}] ", self.instruction_pointer, self.relative_base);
+            let start_ip = self.instruction_pointer;
+            let instruction = Instruction::try_from(self.read_memory(start_ip))?;
 
             if let OpCode::Halt = instruction.op_code {
-                println!("HALT");
-                return true;
+                self.halted = true;
+                return Ok(Some(ComputeResult::Halted));
             }
             self.instruction_pointer += 1;
 
             match instruction.op_code {
                 OpCode::Add => {
-                    let arg1 = self.read_value(&instruction.parameter_modes[0]);
-                    let arg2 = self.read_value(&instruction.parameter_modes[1]);
-                    let addr = self.read_addr(&instruction.parameter_modes[2]);
+                    let arg1 = self.read_value(&instruction.parameter_modes[0])?;
+                    let arg2 = self.read_value(&instruction.parameter_modes[1])?;
+                    let addr = self.read_addr(&instruction.parameter_modes[2])?;
 
-                    println!("ADD {} {} {}", arg1, arg2, addr);
                     self.write_memory(addr,  arg1 + arg2);
                 }
                 OpCode::Mul => {
-                    let arg1 = self.read_value(&instruction.parameter_modes[0]);
-                    let arg2 = self.read_value(&instruction.parameter_modes[1]);
-                    let addr = self.read_addr(&instruction.parameter_modes[2]);
+                    let arg1 = self.read_value(&instruction.parameter_modes[0])?;
+                    let arg2 = self.read_value(&instruction.parameter_modes[1])?;
+                    let addr = self.read_addr(&instruction.parameter_modes[2])?;
 
-                    println!("ADD {} {} {}", arg1, arg2, addr);
                     self.write_memory(addr,  arg1 * arg2);
                 }
                 OpCode::Input => {
-                    let addr = self.read_addr(&instruction.parameter_modes[0]);
-                    let input = self.input.pop_front().expect("Input exhausted!");
+                    let addr = self.read_addr(&instruction.parameter_modes[0])?;
+
+                    let input = match self.input.read() {
+                        Some(input) => input,
+                        None => {
+                            self.instruction_pointer = start_ip;
+                            return Ok(Some(ComputeResult::NeedsInput));
+                        }
+                    };
 
-                    println!("IN {} {}", input, addr);
                     self.write_memory(addr, input);
                 }
                 OpCode::Output => {
-                    let value = self.read_value(&instruction.parameter_modes[0]);
+                    let value = self.read_value(&instruction.parameter_modes[0])?;
 
-                    println!("OUT {}", value);
-                    self.output.push(value);
+                    self.output.write(value);
                 }
                 OpCode::JumpIfZero => {
-                    let test = self.read_value(&instruction.parameter_modes[0]);
-                    let addr = self.read_value(&instruction.parameter_modes[1]) as usize;
+                    let test = self.read_value(&instruction.parameter_modes[0])?;
+                    let addr = self.read_value(&instruction.parameter_modes[1])?;
+                    if addr < 0 {
+                        return Err(ExecutionError::InvalidAddress);
+                    }
+                    let addr = addr as usize;
 
-                    println!("JZ {} {}", test, addr);
                     if test == 0 {
                         self.instruction_pointer = addr
                     }
                 }
                 OpCode::JumpIfNotZero => {
-                    let test = self.read_value(&instruction.parameter_modes[0]);
-                    let addr = self.read_value(&instruction.parameter_modes[1]) as usize;
+                    let test = self.read_value(&instruction.parameter_modes[0])?;
+                    let addr = self.read_value(&instruction.parameter_modes[1])?;
+                    if addr < 0 {
+                        return Err(ExecutionError::InvalidAddress);
+                    }
+                    let addr = addr as usize;
 
-                    println!("JZ {} {}", test, addr);
                     if test != 0 {
                         self.instruction_pointer = addr
                     }
                 }
                 OpCode::StoreIfLessThan => {
-                    let arg1 = self.read_value(&instruction.parameter_modes[0]);
-                    let arg2 = self.read_value(&instruction.parameter_modes[1]);
-                    let addr = self.read_addr(&instruction.parameter_modes[2]);
+                    let arg1 = self.read_value(&instruction.parameter_modes[0])?;
+                    let arg2 = self.read_value(&instruction.parameter_modes[1])?;
+                    let addr = self.read_addr(&instruction.parameter_modes[2])?;
 
-                    println!("SLT {} {} {}", arg1, arg2, addr);
                     self.write_memory(addr, if arg1 < arg2 { 1 } else { 0 });
                 }
                 OpCode::StoreIfEquals => {
-                    let arg1 = self.read_value(&instruction.parameter_modes[0]);
-                    let arg2 = self.read_value(&instruction.parameter_modes[1]);
-                    let addr = self.read_addr(&instruction.parameter_modes[2]);
+                    let arg1 = self.read_value(&instruction.parameter_modes[0])?;
+                    let arg2 = self.read_value(&instruction.parameter_modes[1])?;
+                    let addr = self.read_addr(&instruction.parameter_modes[2])?;
 
-                    println!("SEQ {} {} {}", arg1, arg2, addr);
                     self.write_memory(addr, if arg1 == arg2 { 1 } else { 0 });
                 }
                 OpCode::IncrementRelativeBase => {
-                    let offset = self.read_value(&instruction.parameter_modes[0]);
-                    println!("INCB {}", offset);
+                    let offset = self.read_value(&instruction.parameter_modes[0])?;
 
-                    self.relative_base += offset as usize;
+                    self.relative_base += offset;
                 }
                 OpCode::Halt => unreachable!()
             }
 
-            return false;
+            Ok(None)
         }
 
-        pub(crate) fn run(&mut self) {
-            println!("[MEMORY] {:?}", self.memory);
-            println!("[INPUT] {:?}", self.input);
+        pub(crate) fn run(&mut self) -> Result<ComputeResult, ExecutionError> {
             loop {
-                let should_halt = self.step_forward();
-                if should_halt {
-                    break;
+                if let Some(result) = self.step_forward()? {
+                    return Ok(result);
                 }
             }
-            println!("[OUTPUT] {:?}", self.output);
-            println!("");
+        }
+
+        /// Interactive loop reading simple text commands from stdin:
+        /// `b <addr>` sets a breakpoint, `d <addr>` clears one, `s [n]`
+        /// steps (default 1) instruction(s), `c` runs until the next
+        /// breakpoint or halt, `m <start> <len>` dumps a memory range and
+        /// `reg` prints the current registers. `attach_debugger` is
+        /// optional: if it was never called, `debug` starts from a fresh
+        /// `Debugger` with no breakpoints set.
+        pub fn debug(&mut self) -> Result<ComputeResult, ExecutionError> {
+            let stdin = io::stdin();
+            self.debug_from(&mut stdin.lock())
+        }
+
+        fn debug_from<R: BufRead>(&mut self, commands: &mut R) -> Result<ComputeResult, ExecutionError> {
+            let mut debugger = self.debugger.take().unwrap_or_default();
+
+            let result = loop {
+                print!("(debug) ");
+                io::stdout().flush().ok();
+
+                let mut line = String::new();
+                if commands.read_line(&mut line).unwrap_or(0) == 0 {
+                    break Ok(ComputeResult::Halted);
+                }
+
+                let mut words = line.split_whitespace();
+                match words.next() {
+                    Some("b") => {
+                        if let Some(addr) = words.next().and_then(|w| w.parse().ok()) {
+                            debugger.set_breakpoint(addr);
+                            println!("Breakpoint set at {}", addr);
+                        }
+                    }
+                    Some("d") => {
+                        if let Some(addr) = words.next().and_then(|w| w.parse().ok()) {
+                            debugger.clear_breakpoint(addr);
+                            println!("Breakpoint cleared at {}", addr);
+                        }
+                    }
+                    Some("s") => {
+                        let count = words.next().and_then(|w| w.parse().ok()).unwrap_or(1);
+                        let mut stopped = None;
+                        for _ in 0..count {
+                            println!("{:04}: {}", self.instruction_pointer, self.decode_at(self.instruction_pointer));
+                            match self.step_forward() {
+                                Ok(Some(ComputeResult::Halted)) => {
+                                    println!("Program halted.");
+                                    stopped = Some(Ok(ComputeResult::Halted));
+                                    break;
+                                }
+                                Ok(Some(ComputeResult::NeedsInput)) => {
+                                    println!("Blocked: program is waiting for input.");
+                                    stopped = Some(Ok(ComputeResult::NeedsInput));
+                                    break;
+                                }
+                                Ok(None) => {}
+                                Err(err) => { stopped = Some(Err(err)); break; }
+                            }
+                        }
+                        if let Some(result) = stopped {
+                            break result;
+                        }
+                    }
+                    Some("c") => {
+                        let outcome = loop {
+                            match self.step_forward() {
+                                Ok(Some(ComputeResult::Halted)) => {
+                                    println!("Program halted.");
+                                    break Some(Ok(ComputeResult::Halted));
+                                }
+                                Ok(Some(ComputeResult::NeedsInput)) => {
+                                    println!("Blocked: program is waiting for input.");
+                                    break Some(Ok(ComputeResult::NeedsInput));
+                                }
+                                Ok(None) => {
+                                    if debugger.has_breakpoint(self.instruction_pointer) {
+                                        println!("Breakpoint hit at {}", self.instruction_pointer);
+                                        break None;
+                                    }
+                                }
+                                Err(err) => break Some(Err(err)),
+                            }
+                        };
+                        if let Some(result) = outcome {
+                            break result;
+                        }
+                    }
+                    Some("m") => {
+                        let start = words.next().and_then(|w| w.parse().ok()).unwrap_or(0);
+                        let len = words.next().and_then(|w| w.parse().ok()).unwrap_or(8);
+                        println!("{:?}", self.memory.read_range(start..start + len));
+                    }
+                    Some("reg") => {
+                        println!("IP: {} RB: {}", self.instruction_pointer, self.relative_base);
+                    }
+                    _ => println!("commands: b <addr>, d <addr>, s [n], c, m <start> <len>, reg"),
+                }
+            };
+
+            self.debugger = Some(debugger);
+            result
         }
     }
 
     mod tests {
-        use crate::intcode::computer::Computer;
+        use std::collections::VecDeque;
+        use crate::intcode::computer::{Computer, ComputeResult};
         use crate::memory::memory::RangeAddressable;
+        use crate::error::error::ExecutionError;
 
-        fn run_program(program: Vec<i32>, input: Vec<i32>) -> Computer {
-            let mut computer = Computer::new(program, input);
-            computer.run();
+        fn run_program(program: Vec<i64>, input: Vec<i64>) -> Computer<VecDeque<i64>, Vec<i64>> {
+            let mut computer = Computer::new(program, VecDeque::from(input), vec![]);
+            computer.run().expect("program should run to completion");
             computer
         }
 
@@ -258,5 +408,134 @@ pub mod computer {
                 vec![72, 101, 108, 108, 111, 44, 32, 119, 111, 114, 108, 100, 33, 10]
             );
         }
+
+        #[test]
+        fn test_overflowing_multiplication_needs_i64() {
+            let computer = run_program(vec![1102, 100000, 100000, 5, 99], vec![]);
+            assert_eq!(computer.memory.read_range(5..6), vec![10_000_000_000]);
+        }
+
+        #[test]
+        fn test_run_pauses_on_missing_input_and_resumes() {
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> =
+                Computer::new(vec![3, 0, 4, 0, 3, 0, 4, 0, 99], VecDeque::from(vec![42]), vec![]);
+
+            assert_eq!(computer.run().unwrap(), ComputeResult::NeedsInput);
+            assert_eq!(computer.take_output(), vec![42]);
+
+            computer.push_input(7);
+            assert_eq!(computer.run().unwrap(), ComputeResult::Halted);
+            assert_eq!(computer.take_output(), vec![7]);
+        }
+
+        #[test]
+        fn test_input_opcode_preserves_input_on_invalid_destination_address() {
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> =
+                Computer::new(vec![3, -1, 99], VecDeque::from(vec![42]), vec![]);
+
+            assert_eq!(computer.run(), Err(ExecutionError::InvalidAddress));
+            assert_eq!(computer.input, VecDeque::from(vec![42]));
+        }
+
+        #[test]
+        fn test_run_after_halt_returns_already_halted_error() {
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> =
+                Computer::new(vec![99], VecDeque::new(), vec![]);
+
+            assert_eq!(computer.run(), Ok(ComputeResult::Halted));
+            assert_eq!(computer.run(), Err(ExecutionError::AlreadyHalted));
+        }
+
+        #[test]
+        fn test_negative_relative_base_offset_does_not_overflow() {
+            let computer = run_program(vec![109, 1000, 109, -500, 204, -500, 99], vec![]);
+            assert_eq!(computer.output, vec![109]);
+        }
+
+        #[test]
+        fn test_from_str_parses_a_comma_separated_program() {
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> =
+                Computer::from_str("1,0,0,0,\n99\n", VecDeque::new(), vec![]).unwrap();
+            assert_eq!(computer.run().unwrap(), ComputeResult::Halted);
+            assert_eq!(computer.memory.read_range(0..5), vec![2, 0, 0, 0, 99]);
+        }
+
+        #[test]
+        fn test_debug_reports_blocked_on_missing_input_distinct_from_halt() {
+            use std::io::Cursor;
+
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> =
+                Computer::new(vec![3, 0, 4, 0, 99], VecDeque::new(), vec![]);
+            let mut commands = Cursor::new(b"s 1\n".to_vec());
+
+            assert_eq!(computer.debug_from(&mut commands), Ok(ComputeResult::NeedsInput));
+        }
+
+        #[test]
+        fn test_debug_step_runs_program_to_completion() {
+            use std::io::Cursor;
+
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> =
+                Computer::new(vec![1, 0, 0, 0, 99], VecDeque::new(), vec![]);
+            let mut commands = Cursor::new(b"s 4\n".to_vec());
+
+            assert_eq!(computer.debug_from(&mut commands), Ok(ComputeResult::Halted));
+            assert_eq!(computer.memory.read_range(0..5), vec![2, 0, 0, 0, 99]);
+        }
+
+        #[test]
+        fn test_debug_breakpoint_pauses_before_halt() {
+            use std::io::Cursor;
+
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> = Computer::new(
+                vec![
+                    4, 17, 4, 19, 1001, 17, 1, 17, 8, 17, 18, 16, 1006, 16, 0, 99,
+                    -1, 1, 11, 32
+                ],
+                VecDeque::new(),
+                vec![]
+            );
+            let mut commands = Cursor::new(b"b 0\nc\n".to_vec());
+
+            assert_eq!(computer.debug_from(&mut commands), Ok(ComputeResult::Halted));
+            assert_eq!(computer.output, vec![1, 32]);
+        }
+
+        #[test]
+        fn test_debug_cleared_breakpoint_no_longer_pauses_execution() {
+            use std::io::Cursor;
+
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> = Computer::new(
+                vec![
+                    4, 17, 4, 19, 1001, 17, 1, 17, 8, 17, 18, 16, 1006, 16, 0, 99,
+                    -1, 1, 11, 32
+                ],
+                VecDeque::new(),
+                vec![]
+            );
+            let mut commands = Cursor::new(b"b 0\nd 0\nc\n".to_vec());
+
+            assert_eq!(computer.debug_from(&mut commands), Ok(ComputeResult::Halted));
+            assert_eq!(
+                computer.output,
+                vec![1, 32, 2, 32, 3, 32, 4, 32, 5, 32, 6, 32, 7, 32, 8, 32, 9, 32, 10, 32]
+            );
+        }
+
+        #[test]
+        fn test_pipe_wires_one_computers_output_into_another() {
+            use crate::io::io::Pipe;
+
+            // Reads a value, doubles it and writes it back out.
+            let doubler = vec![3, 0, 1, 0, 0, 0, 4, 0, 99];
+            let pipe = Pipe::shared();
+
+            let mut upstream = Computer::new(doubler.clone(), VecDeque::from(vec![5]), pipe.clone());
+            assert_eq!(upstream.run().unwrap(), ComputeResult::Halted);
+
+            let mut downstream = Computer::new(doubler, pipe, vec![]);
+            assert_eq!(downstream.run().unwrap(), ComputeResult::Halted);
+            assert_eq!(downstream.take_output(), vec![20]);
+        }
     }
 }
@@ -0,0 +1,94 @@
+pub mod io {
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::fmt::Debug;
+    use std::rc::Rc;
+
+    pub trait Input: Debug {
+        fn read(&mut self) -> Option<i64>;
+        fn push(&mut self, value: i64);
+    }
+
+    pub trait Output: Debug {
+        fn write(&mut self, value: i64);
+        fn last(&self) -> Option<i64>;
+    }
+
+    impl Input for VecDeque<i64> {
+        fn read(&mut self) -> Option<i64> {
+            self.pop_front()
+        }
+
+        fn push(&mut self, value: i64) {
+            self.push_back(value);
+        }
+    }
+
+    impl Output for Vec<i64> {
+        fn write(&mut self, value: i64) {
+            self.push(value);
+        }
+
+        fn last(&self) -> Option<i64> {
+            self.as_slice().last().copied()
+        }
+    }
+
+    /// An in-memory channel that is simultaneously the output end of one
+    /// `Computer` and the input end of another, so that values written by
+    /// one VM become readable by the next without an intermediate `Vec`.
+    #[derive(Debug, Default)]
+    pub struct Pipe {
+        buffer: VecDeque<i64>,
+    }
+
+    impl Pipe {
+        pub fn new() -> Pipe {
+            Pipe { buffer: VecDeque::new() }
+        }
+
+        pub fn shared() -> Rc<RefCell<Pipe>> {
+            Rc::new(RefCell::new(Pipe::new()))
+        }
+    }
+
+    impl Input for Pipe {
+        fn read(&mut self) -> Option<i64> {
+            self.buffer.pop_front()
+        }
+
+        fn push(&mut self, value: i64) {
+            self.buffer.push_back(value);
+        }
+    }
+
+    impl Output for Pipe {
+        fn write(&mut self, value: i64) {
+            self.buffer.push_back(value);
+        }
+
+        fn last(&self) -> Option<i64> {
+            self.buffer.back().copied()
+        }
+    }
+
+    impl<T: Input> Input for Rc<RefCell<T>> {
+        fn read(&mut self) -> Option<i64> {
+            self.borrow_mut().read()
+        }
+
+        fn push(&mut self, value: i64) {
+            self.borrow_mut().push(value);
+        }
+    }
+
+    impl<T: Output> Output for Rc<RefCell<T>> {
+        fn write(&mut self, value: i64) {
+            self.borrow_mut().write(value);
+        }
+
+        fn last(&self) -> Option<i64> {
+            self.borrow().last()
+        }
+    }
+}
@@ -0,0 +1,29 @@
+pub mod loader {
+    use std::num::ParseIntError;
+
+    /// Parses a raw Intcode puzzle input (comma-separated integers, with
+    /// arbitrary surrounding/interleaved whitespace and newlines) into a
+    /// program image ready to hand to `Computer::new`.
+    pub fn parse_program(source: &str) -> Result<Vec<i64>, ParseIntError> {
+        source
+            .trim()
+            .split(',')
+            .map(|word| word.trim().parse())
+            .collect()
+    }
+
+    mod tests {
+        use super::parse_program;
+
+        #[test]
+        fn test_parse_program_trims_whitespace_and_newlines() {
+            let source = "1,0,0,0,\n99\n";
+            assert_eq!(parse_program(source), Ok(vec![1, 0, 0, 0, 99]));
+        }
+
+        #[test]
+        fn test_parse_program_rejects_non_numeric_words() {
+            assert!(parse_program("1,two,3").is_err());
+        }
+    }
+}
@@ -1,21 +1,121 @@
 mod intcode;
+mod opcode;
+mod instruction;
+mod memory;
+mod error;
+mod io;
+mod disassembler;
+mod debugger;
+mod loader;
 
-use std::fmt::{Display, Formatter};
-use std::ops::Range;
-use crate::intcode::computer::Computer;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::env;
+use std::fs;
+use std::rc::Rc;
+use crate::intcode::computer::{Computer, ComputeResult};
+use crate::io::io::{Output, Pipe};
+use crate::loader::loader::parse_program;
 
-fn main() {
-    let program = vec![
+fn demo_program() -> Vec<i64> {
+    vec![
         4, 3,
         101, 72, 14, 3,
         101, 1, 4, 4,
         5, 3, 16,
         99,
         29, 7, 0, 3, -67, -12, 87, -8, 3, -6, -8, -67, -23, -10
-    ];
+    ]
+}
+
+/// Reads and parses a raw Intcode puzzle input file (comma-separated
+/// integers) given on the command line.
+fn read_puzzle_input(path: &str) -> Vec<i64> {
+    let source = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", path, err));
+    parse_program(&source).unwrap_or_else(|err| panic!("failed to parse {}: {}", path, err))
+}
+
+fn main() {
+    let mut args = env::args().skip(1);
+
+    match args.next().as_deref() {
+        Some("--debug") => {
+            let source = args.next().map(|path| fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("failed to read {}: {}", path, err)));
+
+            let mut computer: Computer<VecDeque<i64>, Vec<i64>> = match source {
+                Some(source) => Computer::from_str(&source, VecDeque::from(vec![4, 3, 2, 1, 0]), vec![])
+                    .unwrap_or_else(|err| panic!("failed to parse program: {}", err)),
+                None => Computer::new(demo_program(), VecDeque::from(vec![4, 3, 2, 1, 0]), vec![]),
+            };
+            computer.attach_debugger();
+            match computer.debug() {
+                Ok(result) => println!("{:?}", result),
+                Err(err) => eprintln!("Execution failed: {}", err),
+            }
+        }
+        Some(path) => run_demo(read_puzzle_input(path)),
+        None => run_demo(demo_program()),
+    }
+}
+
+fn run_demo(program: Vec<i64>) {
+    println!("-- disassembly --");
+    disassembler::disassembler::print_listing(&program);
+
+    let mut computer: Computer<VecDeque<i64>, Vec<i64>> =
+        Computer::new(program, VecDeque::from(vec![4, 3, 2, 1, 0]), vec![]);
+    match computer.run() {
+        Ok(ComputeResult::Halted) => println!("Output: {:?}", computer.output),
+        Ok(ComputeResult::NeedsInput) => println!("Blocked: program is waiting for input."),
+        Err(err) => eprintln!("Execution failed: {}", err),
+    }
+
+    run_amplifier_chain();
+    run_resumable_echo();
+}
+
+/// Demonstrates pausing on `ComputeResult::NeedsInput` and resuming via
+/// `push_input`: runs until the program blocks, feeds it the next
+/// scripted value, and repeats until it halts.
+fn run_resumable_echo() {
+    let echo = vec![3, 0, 4, 0, 3, 0, 4, 0, 99];
+    let mut computer: Computer<VecDeque<i64>, Vec<i64>> =
+        Computer::new(echo, VecDeque::new(), vec![]);
+    let mut remaining_inputs = VecDeque::from(vec![42, 7]);
+
+    loop {
+        match computer.run() {
+            Ok(ComputeResult::NeedsInput) => {
+                let value = remaining_inputs.pop_front().expect("demo ran out of scripted input");
+                computer.push_input(value);
+            }
+            Ok(ComputeResult::Halted) => break,
+            Err(err) => {
+                eprintln!("Execution failed: {}", err);
+                return;
+            }
+        }
+    }
+
+    println!("Resumable echo output: {:?} (last: {:?})", computer.output, computer.output.last());
+}
+
+/// Chains two copies of a doubling program through a shared `Pipe`, the
+/// same producer/consumer wiring a day-7-style amplifier feedback ring
+/// uses between stages.
+fn run_amplifier_chain() {
+    let doubler = vec![3, 0, 1, 0, 0, 0, 4, 0, 99];
+    let pipe = Pipe::shared();
+
+    let mut upstream: Computer<VecDeque<i64>, Rc<RefCell<Pipe>>> =
+        Computer::new(doubler.clone(), VecDeque::from(vec![5]), pipe.clone());
+    upstream.run().expect("upstream stage should run to completion");
 
-    let mut computer = Computer::new(program, vec![4, 3, 2, 1, 0]);
-    computer.run();
+    let mut downstream: Computer<Rc<RefCell<Pipe>>, Vec<i64>> =
+        Computer::new(doubler, pipe, vec![]);
+    downstream.run().expect("downstream stage should run to completion");
 
-    println!("Output: {:?}", computer.output);
+    println!("Amplifier chain output: {:?}", downstream.take_output());
 }